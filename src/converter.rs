@@ -1,8 +1,8 @@
-use geojson::Value::Point;
+use geojson::Value::{LineString, Point, Polygon};
 use geojson::{Feature, FeatureCollection};
 use gtfs_structures::Gtfs;
 use serde_json::Map;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub fn extract_stops(gtfs: &Gtfs) -> Vec<Feature> {
     // Convert the stops of the GTFS by mapping each field
@@ -43,7 +43,7 @@ pub fn extract_stops(gtfs: &Gtfs) -> Vec<Feature> {
                     (Some(lon), Some(lat)) => Some(geojson::Geometry::new(Point(vec![*lon, *lat]))),
                     _ => None,
                 },
-                id: None,
+                id: Some(geojson::feature::Id::String(stop.id.clone())),
                 bbox: None,
                 properties: Some(info),
                 foreign_members: None,
@@ -89,7 +89,7 @@ pub fn get_new_feature_from_shape(
     Feature {
         bbox: None,
         geometry: geom,
-        id: None,
+        id: Some(geojson::feature::Id::String(shape_id.to_string())),
         properties,
         foreign_members: None,
     }
@@ -116,10 +116,281 @@ pub fn get_route_properties(
             "route_text_color".to_string(),
             format!("{}", route.text_color).into(),
         );
+        if let Some((price, currency, payment_method, transfers, transfer_duration, count)) =
+            get_route_fare(gtfs, route_id)
+        {
+            properties.insert("fare_price".to_string(), price.into());
+            properties.insert("fare_currency".to_string(), currency.into());
+            properties.insert("fare_payment_method".to_string(), payment_method.into());
+            properties.insert("fare_transfers".to_string(), transfers.into());
+            if let Some(transfer_duration) = transfer_duration {
+                properties.insert(
+                    "fare_transfer_duration".to_string(),
+                    transfer_duration.into(),
+                );
+            }
+            properties.insert("fare_rules_count".to_string(), count.into());
+        }
         properties
     })
 }
 
+// Resolves the fare(s) applicable to a route by joining `fare_rules` to `fare_attributes` on
+// `fare_id`. When several rules apply to the same route, the minimum price is reported along
+// with how many rules were found, so a caller can tell a single flat fare from a fare zone.
+#[allow(clippy::type_complexity)]
+fn get_route_fare(
+    gtfs: &Gtfs,
+    route_id: &str,
+) -> Option<(f32, String, String, u16, Option<u32>, usize)> {
+    let matching_attributes: Vec<&gtfs_structures::FareAttribute> = gtfs
+        .fare_rules
+        .iter()
+        .filter(|rule| rule.route_id.as_deref() == Some(route_id))
+        .filter_map(|rule| gtfs.fare_attributes.get(&rule.fare_id))
+        .collect();
+
+    matching_attributes
+        .iter()
+        .min_by(|a, b| {
+            a.price
+                .partial_cmp(&b.price)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|cheapest| {
+            let payment_method = match cheapest.payment_method {
+                gtfs_structures::PaymentMethod::Aboard => "on board".to_string(),
+                gtfs_structures::PaymentMethod::BeforeBoarding => "before boarding".to_string(),
+                gtfs_structures::PaymentMethod::Unknown(u) => u.to_string(),
+            };
+            (
+                cheapest.price,
+                cheapest.currency.clone(),
+                payment_method,
+                cheapest.transfers,
+                cheapest.transfer_duration,
+                matching_attributes.len(),
+            )
+        })
+}
+
+// Given a GTFS reference, builds a LineString feature for each `transfers.txt` entry,
+// linking the two stops it connects. Transfers where either stop has no position are skipped,
+// since there is no geometry to draw.
+pub fn extract_transfers(gtfs: &Gtfs) -> Vec<Feature> {
+    gtfs.transfers
+        .iter()
+        .filter_map(|transfer| {
+            let from_stop = gtfs.stops.get(&transfer.from_stop_id)?;
+            let to_stop = gtfs.stops.get(&transfer.to_stop_id)?;
+            let (from_lon, from_lat) = (from_stop.longitude?, from_stop.latitude?);
+            let (to_lon, to_lat) = (to_stop.longitude?, to_stop.latitude?);
+
+            let mut properties = Map::new();
+            properties.insert(
+                "from_stop_id".to_string(),
+                transfer.from_stop_id.clone().into(),
+            );
+            properties.insert("to_stop_id".to_string(), transfer.to_stop_id.clone().into());
+            properties.insert(
+                "transfer_type".to_string(),
+                format!("{:?}", transfer.transfer_type).into(),
+            );
+            if let Some(min_transfer_time) = transfer.min_transfer_time {
+                properties.insert("min_transfer_time".to_string(), min_transfer_time.into());
+            }
+
+            Some(Feature {
+                bbox: None,
+                geometry: Some(geojson::Geometry::new(LineString(vec![
+                    vec![from_lon, from_lat],
+                    vec![to_lon, to_lat],
+                ]))),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            })
+        })
+        .collect()
+}
+
+// Given a GTFS reference, groups child stops by `parent_station` and emits one footprint
+// feature per station: a convex hull Polygon when the positioned stops have at least 3
+// distinct, non-collinear positions, falling back to a Point or a LineString otherwise.
+pub fn extract_parent_station_polygons(gtfs: &Gtfs) -> Vec<Feature> {
+    let mut children_by_parent: HashMap<&str, Vec<[f64; 2]>> = HashMap::new();
+    for stop in gtfs.stops.values() {
+        if let (Some(parent), Some(lon), Some(lat)) =
+            (&stop.parent_station, stop.longitude, stop.latitude)
+        {
+            children_by_parent
+                .entry(parent.as_str())
+                .or_default()
+                .push([lon, lat]);
+        }
+    }
+
+    children_by_parent
+        .into_iter()
+        .map(|(parent_station, points)| {
+            let name = gtfs
+                .stops
+                .get(parent_station)
+                .map(|stop| stop.name.clone())
+                .unwrap_or_default();
+            let stop_count = points.len();
+
+            let mut properties = Map::new();
+            properties.insert("parent_station".to_string(), parent_station.into());
+            properties.insert("stop_count".to_string(), stop_count.into());
+            properties.insert("name".to_string(), name.into());
+
+            let geometry = match stop_count {
+                0 => unreachable!("a parent_station entry always has at least one child stop"),
+                1 => geojson::Geometry::new(Point(points[0].to_vec())),
+                2 => {
+                    geojson::Geometry::new(LineString(points.iter().map(|p| p.to_vec()).collect()))
+                }
+                _ => {
+                    // Collinear points, or points that collapse to <3 distinct positions once
+                    // `convex_hull` dedups them, yield a hull with fewer than 3 points; a
+                    // Polygon ring needs at least 3 distinct vertices, so fall back the same way
+                    // the 1- and 2-stop cases above do.
+                    let hull = convex_hull(points);
+                    match hull.len() {
+                        1 => geojson::Geometry::new(Point(hull[0].to_vec())),
+                        2 => geojson::Geometry::new(LineString(
+                            hull.iter().map(|p| p.to_vec()).collect(),
+                        )),
+                        _ => {
+                            let mut ring: Vec<Vec<f64>> =
+                                hull.into_iter().map(|p| p.to_vec()).collect();
+                            ring.push(ring[0].clone());
+                            geojson::Geometry::new(Polygon(vec![ring]))
+                        }
+                    }
+                }
+            };
+
+            Feature {
+                bbox: None,
+                geometry: Some(geometry),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            }
+        })
+        .collect()
+}
+
+// Andrew's monotone-chain convex hull: sort points lexicographically by (lon, lat), then build
+// the lower and upper hull chains, popping the last point of the chain whenever the last three
+// points make a non-left turn (cross product <= 0).
+fn convex_hull(mut points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    points.sort_by(|a, b| {
+        a[0].partial_cmp(&b[0])
+            .unwrap()
+            .then(a[1].partial_cmp(&b[1]).unwrap())
+    });
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    fn cross(o: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    }
+
+    let mut lower: Vec<[f64; 2]> = Vec::new();
+    for &p in &points {
+        while lower.len() >= 2 && cross(lower[lower.len() - 2], lower[lower.len() - 1], p) <= 0.0 {
+            lower.pop();
+        }
+        lower.push(p);
+    }
+
+    let mut upper: Vec<[f64; 2]> = Vec::new();
+    for &p in points.iter().rev() {
+        while upper.len() >= 2 && cross(upper[upper.len() - 2], upper[upper.len() - 1], p) <= 0.0 {
+            upper.pop();
+        }
+        upper.push(p);
+    }
+
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+// Recursively collects every coordinate pair nested in a geometry's value, regardless of its
+// variant, so a bbox can be folded over them uniformly.
+fn collect_positions<'a>(value: &'a geojson::Value, out: &mut Vec<&'a Vec<f64>>) {
+    use geojson::Value::*;
+    match value {
+        Point(p) => out.push(p),
+        MultiPoint(points) | LineString(points) => out.extend(points.iter()),
+        MultiLineString(lines) | Polygon(lines) => {
+            lines.iter().for_each(|line| out.extend(line.iter()))
+        }
+        MultiPolygon(polygons) => polygons
+            .iter()
+            .for_each(|polygon| polygon.iter().for_each(|line| out.extend(line.iter()))),
+        GeometryCollection(geometries) => geometries
+            .iter()
+            .for_each(|geometry| collect_positions(&geometry.value, out)),
+    }
+}
+
+/// Computes the `[min_lon, min_lat, max_lon, max_lat]` bbox of a geometry, or `None` when it
+/// has no coordinates.
+pub fn geometry_bbox(geometry: &geojson::Geometry) -> Option<geojson::Bbox> {
+    let mut positions = Vec::new();
+    collect_positions(&geometry.value, &mut positions);
+    positions.into_iter().fold(None, |acc, position| {
+        let (lon, lat) = (position[0], position[1]);
+        Some(match acc {
+            None => vec![lon, lat, lon, lat],
+            Some(bbox) => vec![
+                bbox[0].min(lon),
+                bbox[1].min(lat),
+                bbox[2].max(lon),
+                bbox[3].max(lat),
+            ],
+        })
+    })
+}
+
+// Folds a feature's bbox into a running union bbox, starting the union from `bbox` when there
+// is none yet. Features without a bbox are skipped by the caller.
+fn union_bbox(acc: Option<geojson::Bbox>, bbox: &geojson::Bbox) -> Option<geojson::Bbox> {
+    Some(match acc {
+        None => bbox.clone(),
+        Some(acc) => vec![
+            acc[0].min(bbox[0]),
+            acc[1].min(bbox[1]),
+            acc[2].max(bbox[2]),
+            acc[3].max(bbox[3]),
+        ],
+    })
+}
+
+/// Populates `bbox` on every feature of the collection and on the collection itself, computed
+/// from each feature's geometry. Features with no geometry keep a `None` bbox and do not
+/// contribute to the collection-level union.
+pub fn with_bboxes(mut collection: FeatureCollection) -> FeatureCollection {
+    let mut collection_bbox = None;
+    for feature in collection.features.iter_mut() {
+        feature.bbox = feature.geometry.as_ref().and_then(geometry_bbox);
+        if let Some(bbox) = &feature.bbox {
+            collection_bbox = union_bbox(collection_bbox, bbox);
+        }
+    }
+    collection.bbox = collection_bbox;
+    collection
+}
+
 /// This function will take a GTFS data format and ouput a FeatureCollection, which can in turn, be printed by the utility module.
 /// # Examples
 /// ```
@@ -130,9 +401,133 @@ pub fn convert_to_geojson(gtfs_data: &Gtfs) -> FeatureCollection {
     let mut features = extract_stops(gtfs_data);
     let shape_features = extract_trips_shapes(gtfs_data);
     features.extend(shape_features);
+    features.extend(extract_transfers(gtfs_data));
     FeatureCollection {
         bbox: None,
         features,
         foreign_members: None,
     }
 }
+
+/// Same conversion as [`convert_to_geojson`], but writes the features to `writer` kind by kind
+/// instead of assembling a `FeatureCollection` in memory first. Each extractor (stops, shapes,
+/// transfers, station footprints) still builds the full `Vec` of its own features before they
+/// are written, so memory peaks at the largest of these kinds rather than at the whole
+/// collection; features are not produced and dropped one at a time.
+///
+/// When `with_bbox` is set, each feature's own bbox is still computed and attached as it is
+/// written, but no collection-level union bbox is emitted: because features are never held
+/// together in memory, there is nothing to fold a union over.
+/// # Examples
+/// ```
+/// let gtfs_data = gtfs_structures::Gtfs::new("test/basic/gtfs").unwrap();
+/// let mut out = Vec::new();
+/// gtfs_geojson::convert_to_geojson_streaming(&gtfs_data, &mut out, false, false, &[]).unwrap();
+/// ```
+pub fn convert_to_geojson_streaming<W: std::io::Write>(
+    gtfs_data: &Gtfs,
+    writer: W,
+    with_station_polygons: bool,
+    with_bbox: bool,
+    extra_features: &[Feature],
+) -> geojson::Result<()> {
+    let mut writer = geojson::FeatureWriter::from_writer(writer);
+    let mut write = |mut feature: Feature| -> geojson::Result<()> {
+        if with_bbox {
+            feature.bbox = feature.geometry.as_ref().and_then(geometry_bbox);
+        }
+        writer.write_feature(&feature)
+    };
+    for feature in extract_stops(gtfs_data) {
+        write(feature)?;
+    }
+    for feature in extract_trips_shapes(gtfs_data) {
+        write(feature)?;
+    }
+    for feature in extract_transfers(gtfs_data) {
+        write(feature)?;
+    }
+    if with_station_polygons {
+        for feature in extract_parent_station_polygons(gtfs_data) {
+            write(feature)?;
+        }
+    }
+    for feature in extra_features.iter().cloned() {
+        write(feature)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_bbox_of_a_point_is_a_degenerate_box() {
+        let geometry = geojson::Geometry::new(Point(vec![1.0, 47.0]));
+        assert_eq!(geometry_bbox(&geometry), Some(vec![1.0, 47.0, 1.0, 47.0]));
+    }
+
+    #[test]
+    fn get_route_fare_reports_the_cheapest_of_several_rules() {
+        let gtfs = Gtfs::new("test/fares/gtfs/").unwrap();
+        let (price, _, _, _, _, count) = get_route_fare(&gtfs, "route1").unwrap();
+        assert_eq!(price, 1.5);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn convex_hull_of_a_square_keeps_all_four_corners() {
+        let points = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let hull = convex_hull(points);
+        assert_eq!(hull.len(), 4);
+    }
+
+    #[test]
+    fn convex_hull_of_collinear_points_collapses_to_the_two_endpoints() {
+        let points = vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+        let hull = convex_hull(points);
+        assert_eq!(hull, vec![[0.0, 0.0], [2.0, 0.0]]);
+    }
+
+    #[test]
+    fn parent_station_footprints_match_their_child_stop_count() {
+        let gtfs = Gtfs::new("test/stations/gtfs/").unwrap();
+        let footprints = extract_parent_station_polygons(&gtfs);
+
+        let footprint = |parent_station: &str| {
+            footprints
+                .iter()
+                .find(|f| {
+                    f.properties
+                        .as_ref()
+                        .unwrap()
+                        .get("parent_station")
+                        .and_then(|id| id.as_str())
+                        == Some(parent_station)
+                })
+                .unwrap()
+                .geometry
+                .as_ref()
+                .unwrap()
+                .value
+                .clone()
+        };
+
+        // 4 square child stops produce a closed, 5-position Polygon ring.
+        match footprint("station_square") {
+            Polygon(rings) => assert_eq!(rings[0].len(), 5),
+            other => panic!("expected a Polygon, got {other:?}"),
+        }
+
+        // 3 collinear child stops have no hull area, so this falls back to a LineString.
+        match footprint("station_line") {
+            LineString(positions) => assert_eq!(positions.len(), 2),
+            other => panic!("expected a LineString, got {other:?}"),
+        }
+
+        assert!(matches!(footprint("station_single"), Point(_)));
+        assert!(matches!(footprint("station_double"), LineString(_)));
+    }
+}
@@ -5,6 +5,7 @@ use gtfs_structures::GtfsReader;
 use std::path::PathBuf;
 
 mod converter;
+mod realtime;
 mod utility;
 
 #[derive(Parser, Debug)]
@@ -27,6 +28,22 @@ struct Opt {
         parse(from_os_str)
     )]
     output_file: Option<PathBuf>,
+    #[clap(
+        long = "stations",
+        help = "Emit a footprint for each parent_station, built from its child stops: a convex-hull polygon, or a point or line when they have fewer than 3 distinct or only collinear positions"
+    )]
+    stations: bool,
+    #[clap(
+        name = "rt",
+        long = "rt",
+        help = "Path or URL to a GTFS-Realtime VehiclePositions feed to overlay as live vehicle points"
+    )]
+    realtime_vehicle_positions: Option<String>,
+    #[clap(
+        long = "bbox",
+        help = "Compute and attach a bounding box to every feature and to the collection"
+    )]
+    bbox: bool,
 }
 
 fn main() {
@@ -42,14 +59,60 @@ fn main() {
         )
         .expect("The GTFS file is not well formated.");
 
-    println!("Extracting Spatial features");
-    let stops_as_features = crate::converter::convert_to_geojson(&gtfs);
+    let vehicle_features = opt
+        .realtime_vehicle_positions
+        .as_deref()
+        .map(|path_or_url| {
+            println!("Fetching GTFS-Realtime vehicle positions");
+            let feed = realtime::read_feed_message(path_or_url)
+                .expect("Could not read the GTFS-Realtime feed.");
+            realtime::extract_vehicle_positions(&gtfs, &feed)
+        })
+        .unwrap_or_default();
 
-    println!("Saving GeoJSON");
+    println!("Extracting Spatial features");
     match opt.output_file {
-        Some(f) => utility::save_to_file(&stops_as_features, &f),
-        None => println!("{}", stops_as_features),
+        // `convert_to_geojson_streaming` never computes a collection-level union bbox, since it
+        // writes each feature out as it goes rather than holding the collection in memory. Fall
+        // back to the in-memory path so `--bbox -o` doesn't silently drop the requested bbox.
+        Some(f) if opt.bbox => {
+            println!("Saving GeoJSON");
+            let collection =
+                build_feature_collection(&gtfs, opt.stations, opt.bbox, vehicle_features);
+            utility::save_to_file(&collection, &f);
+        }
+        Some(f) => {
+            println!("Saving GeoJSON");
+            utility::stream_to_file(&gtfs, &f, opt.stations, opt.bbox, &vehicle_features);
+        }
+        None => {
+            let collection =
+                build_feature_collection(&gtfs, opt.stations, opt.bbox, vehicle_features);
+            println!("{}", collection);
+        }
+    }
+}
+
+// Assembles the whole `FeatureCollection` in memory: stops, shapes and transfers, optionally station
+// footprints and live vehicle positions, then optionally bboxes. Used by every output path that
+// isn't the streaming one.
+fn build_feature_collection(
+    gtfs: &gtfs_structures::Gtfs,
+    stations: bool,
+    bbox: bool,
+    vehicle_features: Vec<geojson::Feature>,
+) -> geojson::FeatureCollection {
+    let mut collection = crate::converter::convert_to_geojson(gtfs);
+    if stations {
+        collection
+            .features
+            .extend(crate::converter::extract_parent_station_polygons(gtfs));
+    }
+    collection.features.extend(vehicle_features);
+    if bbox {
+        collection = crate::converter::with_bboxes(collection);
     }
+    collection
 }
 
 #[cfg(test)]
@@ -59,7 +122,10 @@ mod test {
     #[test]
     fn with_code_test() {
         use crate::converter::convert_to_geojson;
-        let gtfs = gtfs_structures::GtfsReader::default().read_stop_times(true).read("test/basic/gtfs/").unwrap();
+        let gtfs = gtfs_structures::GtfsReader::default()
+            .read_stop_times(true)
+            .read("test/basic/gtfs/")
+            .unwrap();
         let geojson = convert_to_geojson(&gtfs);
 
         let given_feature = &geojson.features.into_iter().find(|f| {
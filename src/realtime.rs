@@ -0,0 +1,76 @@
+//! This module reads a GTFS-Realtime `VehiclePositions` feed and turns it into GeoJSON point
+//! features, joined back to the static route information for styling.
+
+use crate::converter::get_route_properties;
+use geojson::Feature;
+use geojson::Value::Point;
+use gtfs_structures::Gtfs;
+
+/// Reads a GTFS-Realtime `FeedMessage` from a local file path or an HTTP(S) URL.
+/// # Examples
+/// ```
+/// let feed = gtfs_geojson::realtime::read_feed_message("vehicle_positions.pb").unwrap();
+/// ```
+pub fn read_feed_message(
+    path_or_url: &str,
+) -> Result<gtfs_rt::FeedMessage, Box<dyn std::error::Error>> {
+    let bytes = if path_or_url.starts_with("http://") || path_or_url.starts_with("https://") {
+        reqwest::blocking::get(path_or_url)?.bytes()?.to_vec()
+    } else {
+        std::fs::read(path_or_url)?
+    };
+    Ok(prost::Message::decode(bytes.as_slice())?)
+}
+
+// Given a static GTFS reference and a decoded GTFS-Realtime feed, builds a Point feature for
+// each entity that carries a `vehicle` field with a `position`; vehicles without a position are
+// skipped. Each vehicle's `trip.route_id` is joined through
+// `get_route_properties` so live vehicles inherit route color and name, then overlaid with
+// live properties.
+pub fn extract_vehicle_positions(gtfs: &Gtfs, feed: &gtfs_rt::FeedMessage) -> Vec<Feature> {
+    feed.entity
+        .iter()
+        .filter_map(|entity| {
+            let vehicle = entity.vehicle.as_ref()?;
+            let position = vehicle.position.as_ref()?;
+
+            let mut properties = vehicle
+                .trip
+                .as_ref()
+                .and_then(|trip| trip.route_id.as_deref())
+                .and_then(|route_id| get_route_properties(gtfs, route_id))
+                .unwrap_or_default();
+
+            if let Some(vehicle_id) = vehicle.vehicle.as_ref().and_then(|v| v.id.as_ref()) {
+                properties.insert("vehicle_id".to_string(), vehicle_id.clone().into());
+            }
+            if let Some(trip_id) = vehicle.trip.as_ref().and_then(|trip| trip.trip_id.as_ref()) {
+                properties.insert("trip_id".to_string(), trip_id.clone().into());
+            }
+            if let Some(bearing) = position.bearing {
+                properties.insert("bearing".to_string(), bearing.into());
+            }
+            if let Some(speed) = position.speed {
+                properties.insert("speed".to_string(), speed.into());
+            }
+            if let Some(timestamp) = vehicle.timestamp {
+                properties.insert("timestamp".to_string(), timestamp.into());
+            }
+
+            Some(Feature {
+                bbox: None,
+                geometry: Some(geojson::Geometry::new(Point(vec![
+                    position.longitude as f64,
+                    position.latitude as f64,
+                ]))),
+                id: vehicle
+                    .vehicle
+                    .as_ref()
+                    .and_then(|v| v.id.clone())
+                    .map(geojson::feature::Id::String),
+                properties: Some(properties),
+                foreign_members: None,
+            })
+        })
+        .collect()
+}
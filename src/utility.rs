@@ -1,4 +1,3 @@
-
 use gtfs_structures::Gtfs;
 use std::path::PathBuf;
 
@@ -54,3 +53,29 @@ pub fn print_stops(gtfs_data: &Gtfs) {
 pub fn save_to_file(geotype_collection: &geojson::FeatureCollection, filename_geo: &PathBuf) {
     std::fs::write(filename_geo, geotype_collection.to_string()).expect("Unable to write file");
 }
+
+/// This function will stream the GTFS conversion straight to the file given to it, writing the
+/// features kind by kind instead of building the whole `FeatureCollection` in memory first.
+/// # Examples
+/// ```
+/// let gtfs_data = Gtfs::new("test/basic/gtfs").unwrap();
+/// let path = PathBuf::new();
+/// stream_to_file(&gtfs_data, &path, false, false, &[]);
+/// ```
+pub fn stream_to_file(
+    gtfs_data: &Gtfs,
+    filename_geo: &PathBuf,
+    with_station_polygons: bool,
+    with_bbox: bool,
+    extra_features: &[geojson::Feature],
+) {
+    let file = std::fs::File::create(filename_geo).expect("Unable to create file");
+    crate::converter::convert_to_geojson_streaming(
+        gtfs_data,
+        std::io::BufWriter::new(file),
+        with_station_polygons,
+        with_bbox,
+        extra_features,
+    )
+    .expect("Unable to write file");
+}